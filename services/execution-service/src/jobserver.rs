@@ -0,0 +1,85 @@
+//! GNU make jobserver protocol support for child build tools.
+//!
+//! [`crate::execution_semaphore`] bounds how many `/execute` requests run at
+//! once, but says nothing about how many processes a single build spawns
+//! internally (`make -j8`, `cargo build` shelling out to `rustc` per crate,
+//! ...). The jobserver protocol lets cooperating tools share one token pool
+//! instead of each assuming the whole CPU to itself: we pre-fill a named
+//! pipe with `JOBSERVER_TOKENS` single-byte tokens and advertise it via
+//! `MAKEFLAGS` using the `fifo:` form, which recent `make` and `cargo` both
+//! understand and which — unlike the classic `R,W` fd-pair form — needs no
+//! fd-inheritance dance across `fork`/`exec`, since the child just opens the
+//! path itself.
+
+use std::ffi::CString;
+use std::io;
+use std::process::Command;
+use std::sync::OnceLock;
+
+static JOBSERVER_FIFO: OnceLock<String> = OnceLock::new();
+
+/// Creates the process-wide jobserver FIFO on first use and returns its path;
+/// later calls reuse the same FIFO so every child across every request draws
+/// from one shared token pool. Sized via `JOBSERVER_TOKENS` (default 4).
+fn jobserver_fifo() -> io::Result<&'static str> {
+    if let Some(path) = JOBSERVER_FIFO.get() {
+        return Ok(path);
+    }
+
+    let tokens: u32 = std::env::var("JOBSERVER_TOKENS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(4);
+
+    let path = format!("/tmp/codecollab_jobserver_{}", std::process::id());
+    let path_c = CString::new(path.clone()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    if unsafe { libc::mkfifo(path_c.as_ptr(), 0o600) } != 0 {
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::AlreadyExists {
+            return Err(err);
+        }
+    }
+
+    // Opened read-write so the FIFO always has a writer (a read-only open
+    // would otherwise block until one shows up), and so we can pre-fill the
+    // token pool right here. Deliberately never closed: it needs to stay
+    // open for the life of the process.
+    let fd = unsafe { libc::open(path_c.as_ptr(), libc::O_RDWR) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let token_bytes = vec![b'+'; tokens as usize];
+    if unsafe { libc::write(fd, token_bytes.as_ptr() as *const _, token_bytes.len()) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(JOBSERVER_FIFO.get_or_init(|| path))
+}
+
+/// Advertises the shared jobserver FIFO to `cmd` via `MAKEFLAGS` so any
+/// `make`/`cargo` it (or something it shells out to) invokes acquires/
+/// releases tokens from the shared pool rather than assuming unlimited
+/// parallelism. Failing to set up the jobserver is logged and otherwise
+/// non-fatal — the child just runs without a token budget, same as today.
+///
+/// `sandbox` must be `false`: the FIFO lives at `/tmp/codecollab_jobserver_*`
+/// on the host, but a sandboxed child is `pivot_root`ed into a rootfs that
+/// only bind-mounts [`crate::sandbox`]'s `TOOLCHAIN_DIRS` — host `/tmp` isn't
+/// reachable in there, so the child could never open the path anyway.
+/// Skipped (logged, non-fatal) rather than wired through a bind mount, since
+/// `sandbox` defaults to on and is the common case.
+pub fn configure(cmd: &mut Command, sandbox: bool) {
+    if sandbox {
+        log::debug!("Jobserver skipped: sandboxed children can't reach the host FIFO path");
+        return;
+    }
+
+    match jobserver_fifo() {
+        Ok(path) => {
+            cmd.env("MAKEFLAGS", format!("--jobserver-auth=fifo:{} -j", path));
+        }
+        Err(e) => {
+            log::warn!("Jobserver unavailable, child build tools will not be token-limited: {}", e);
+        }
+    }
+}
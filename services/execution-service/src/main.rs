@@ -1,7 +1,20 @@
+mod jobserver;
+mod project;
+mod pty;
+mod sandbox;
+mod streaming;
+
 use actix_web::{web, App, HttpResponse, HttpServer, Responder};
 use actix_cors::Cors;
 use serde::{Deserialize, Serialize};
 use std::env;
+use std::path::Path;
+use std::sync::OnceLock;
+use tokio::sync::Semaphore;
+
+fn default_sandbox() -> bool {
+    true
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 struct ExecuteRequest {
@@ -9,6 +22,34 @@ struct ExecuteRequest {
     language: String,
     #[serde(default)]
     timeout: u64,
+    /// Maximum CPU time the child may consume, in seconds (`RLIMIT_CPU`).
+    #[serde(default)]
+    cpu_seconds: Option<u64>,
+    /// Maximum address space the child may map, in bytes (`RLIMIT_AS`).
+    #[serde(default)]
+    memory_bytes: Option<u64>,
+    /// Captured stdout/stderr is truncated past this many bytes each.
+    #[serde(default)]
+    max_output_bytes: Option<usize>,
+    /// Maximum number of processes/threads the child's user may own
+    /// (`RLIMIT_NPROC`). The kernel enforces this per real UID across every
+    /// process that UID owns, not per execution — since every unsandboxed
+    /// child runs as this service's own UID, a low value here can also fail
+    /// unrelated executions running concurrently under the same UID, not
+    /// just this one. Only meaningful as a genuinely per-call cap when
+    /// `sandbox` is also on, since `CLONE_NEWUSER` gives the child its own
+    /// UID mapping and its own share of this limit.
+    #[serde(default)]
+    max_processes: Option<u64>,
+    /// Run the child inside the namespace sandbox (see `sandbox.rs`). Defaults
+    /// to on; trusted internal callers can pass `false` to skip it.
+    #[serde(default = "default_sandbox")]
+    sandbox: bool,
+    /// Written to the child's stdin and then closed. Without this, the child's
+    /// stdin pipe is closed before any input is written, so a program calling
+    /// `input()`/`scanf`/`readLine` reads EOF.
+    #[serde(default)]
+    stdin: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -17,6 +58,16 @@ struct ExecuteResponse {
     stderr: String,
     exit_code: i32,
     execution_time: f64,
+    truncated: bool,
+    /// Signal that killed the process (`SIGSEGV`, `SIGKILL`, ...), if it
+    /// didn't exit normally. `exit_code` collapses this case to `1`, which
+    /// hides crashes, OOM kills, and our own resource-limit kills behind an
+    /// identical-looking generic failure.
+    signal: Option<i32>,
+    /// Human-readable cause when `signal` is set, e.g. `"Segmentation fault
+    /// (SIGSEGV)"` or `"Killed — memory limit or forced termination
+    /// (SIGKILL)"`.
+    termination_reason: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -32,6 +83,192 @@ struct HealthCheck {
     service: String,
 }
 
+/// Resource caps applied to a spawned child on Unix via `setrlimit`. `None` leaves
+/// the corresponding limit at whatever the host process already has.
+#[derive(Debug, Clone, Default)]
+struct ResourceLimits {
+    cpu_seconds: Option<u64>,
+    memory_bytes: Option<u64>,
+    max_output_bytes: Option<usize>,
+    max_processes: Option<u64>,
+}
+
+impl From<&ExecuteRequest> for ResourceLimits {
+    fn from(req: &ExecuteRequest) -> Self {
+        ResourceLimits {
+            cpu_seconds: req.cpu_seconds,
+            memory_bytes: req.memory_bytes,
+            max_output_bytes: req.max_output_bytes,
+            max_processes: req.max_processes,
+        }
+    }
+}
+
+/// Open file descriptor cap applied to every spawned child, independent of the
+/// caller-supplied limits, so a descriptor leak can't exhaust the host.
+#[cfg(unix)]
+const DEFAULT_NOFILE_LIMIT: u64 = 256;
+
+#[cfg(unix)]
+fn set_rlimit(resource: libc::__rlimit_resource_t, limit: u64) -> std::io::Result<()> {
+    let rlimit = libc::rlimit {
+        rlim_cur: limit as libc::rlim_t,
+        rlim_max: limit as libc::rlim_t,
+    };
+    if unsafe { libc::setrlimit(resource, &rlimit) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Registers a `pre_exec` hook that drops the child's resource ceilings before
+/// `exec`. Only async-signal-safe calls are allowed inside that closure, so it's
+/// limited to raw `setrlimit` syscalls.
+#[cfg(unix)]
+fn apply_resource_limits(cmd: &mut std::process::Command, limits: ResourceLimits) {
+    use std::os::unix::process::CommandExt;
+
+    unsafe {
+        cmd.pre_exec(move || {
+            if let Some(cpu_seconds) = limits.cpu_seconds {
+                set_rlimit(libc::RLIMIT_CPU, cpu_seconds)?;
+            }
+            if let Some(memory_bytes) = limits.memory_bytes {
+                set_rlimit(libc::RLIMIT_AS, memory_bytes)?;
+            }
+            // max_output_bytes is deliberately not wired to RLIMIT_FSIZE: that
+            // rlimit bounds writes to regular files, not the stdout/stderr
+            // pipes this actually captures from, so it gave zero protection
+            // for its stated purpose and instead silently capped any file
+            // the child happened to write at an unrelated size. Captured
+            // output is truncated in-process by `truncate_output` instead;
+            // add a distinct field here if an actual disk-write cap is ever
+            // wanted.
+            // RLIMIT_NPROC is enforced by the kernel per real UID across every
+            // process that UID owns, not scoped to this child's own process
+            // tree. Every unsandboxed child shares the service's UID, so this
+            // is a global cap shared with whatever else is concurrently
+            // executing, not a true per-call bound — callers relying on it to
+            // isolate one execution from another should also set `sandbox`,
+            // which gives the child its own UID mapping via `CLONE_NEWUSER`.
+            if let Some(max_processes) = limits.max_processes {
+                set_rlimit(libc::RLIMIT_NPROC, max_processes)?;
+            }
+            set_rlimit(libc::RLIMIT_NOFILE, DEFAULT_NOFILE_LIMIT)?;
+            Ok(())
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn apply_resource_limits(_cmd: &mut std::process::Command, _limits: ResourceLimits) {}
+
+/// Puts the child in its own process group so a timeout can kill the whole tree
+/// (the child plus anything it forked, e.g. a compiler it shelled out to) instead
+/// of just the immediate process.
+#[cfg(unix)]
+fn set_process_group(cmd: &mut std::process::Command) {
+    use std::os::unix::process::CommandExt;
+
+    unsafe {
+        cmd.pre_exec(|| {
+            if libc::setpgid(0, 0) != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn set_process_group(_cmd: &mut std::process::Command) {}
+
+/// Sends `SIGKILL` to the negated pgid, killing the child and every descendant it
+/// spawned. `pid` is the child's own pid, which doubles as its pgid since
+/// `set_process_group` calls `setpgid(0, 0)` before `exec`.
+#[cfg(unix)]
+fn kill_process_group(pid: u32) {
+    unsafe {
+        libc::kill(-(pid as i32), libc::SIGKILL);
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(_pid: u32) {}
+
+/// Turns a finished child's raw output into the pieces `ExecuteResponse` needs,
+/// truncating stdout/stderr independently at `max_output_bytes` when set and
+/// surfacing the killing signal (if any) distinctly from the exit code.
+fn truncate_output(
+    output: std::process::Output,
+    max_output_bytes: Option<usize>,
+) -> (String, String, i32, bool, Option<i32>, Option<String>) {
+    let mut stdout_bytes = output.stdout;
+    let mut stderr_bytes = output.stderr;
+    let mut truncated = false;
+
+    if let Some(limit) = max_output_bytes {
+        if stdout_bytes.len() > limit {
+            stdout_bytes.truncate(limit);
+            truncated = true;
+        }
+        if stderr_bytes.len() > limit {
+            stderr_bytes.truncate(limit);
+            truncated = true;
+        }
+    }
+
+    let stdout = String::from_utf8_lossy(&stdout_bytes).to_string();
+    let stderr = String::from_utf8_lossy(&stderr_bytes).to_string();
+    let exit_code = output.status.code().unwrap_or(1);
+    let signal = signal_of(&output.status);
+    let termination_reason = signal.map(describe_signal);
+    (stdout, stderr, exit_code, truncated, signal, termination_reason)
+}
+
+/// The signal that terminated the child, if it didn't exit normally.
+#[cfg(unix)]
+fn signal_of(status: &std::process::ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal()
+}
+
+#[cfg(not(unix))]
+fn signal_of(_status: &std::process::ExitStatus) -> Option<i32> {
+    None
+}
+
+#[cfg(unix)]
+fn describe_signal(sig: i32) -> String {
+    match sig {
+        libc::SIGSEGV => "Segmentation fault (SIGSEGV)".to_string(),
+        libc::SIGKILL => "Killed — memory limit or forced termination (SIGKILL)".to_string(),
+        libc::SIGFPE => "Floating point exception (SIGFPE)".to_string(),
+        libc::SIGABRT => "Aborted (SIGABRT)".to_string(),
+        libc::SIGBUS => "Bus error (SIGBUS)".to_string(),
+        libc::SIGILL => "Illegal instruction (SIGILL)".to_string(),
+        libc::SIGTERM => "Terminated (SIGTERM)".to_string(),
+        other => format!("Terminated by signal {}", other),
+    }
+}
+
+#[cfg(not(unix))]
+fn describe_signal(sig: i32) -> String {
+    format!("Terminated by signal {}", sig)
+}
+
+/// Writes `input` (if any) to the child's stdin and drops the handle so the
+/// child sees EOF, instead of hanging forever on its next read. Must run
+/// before the child is moved into `wait_with_output`.
+fn write_stdin(child: &mut std::process::Child, input: &Option<String>) {
+    if let Some(data) = input {
+        if let Some(mut stdin) = child.stdin.take() {
+            use std::io::Write;
+            let _ = stdin.write_all(data.as_bytes());
+        }
+    }
+}
+
 async fn root() -> impl Responder {
     HttpResponse::Ok().json(ServiceInfo {
         service: "execution-service".to_string(),
@@ -47,35 +284,70 @@ async fn health() -> impl Responder {
     })
 }
 
+/// Caps how many `/execute` requests spawn processes at once; beyond that,
+/// callers get a `429` instead of piling up and thrashing the host with
+/// concurrent compilers. Configurable via `MAX_CONCURRENT_EXECUTIONS`
+/// (default 8).
+static EXECUTION_SEMAPHORE: OnceLock<Semaphore> = OnceLock::new();
+
+fn execution_semaphore() -> &'static Semaphore {
+    EXECUTION_SEMAPHORE.get_or_init(|| {
+        let permits = env::var("MAX_CONCURRENT_EXECUTIONS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(8);
+        Semaphore::new(permits)
+    })
+}
+
 async fn execute_code(req: web::Json<ExecuteRequest>) -> impl Responder {
+    let _permit = match execution_semaphore().try_acquire() {
+        Ok(permit) => permit,
+        Err(_) => {
+            return HttpResponse::TooManyRequests().json(ExecuteResponse {
+                stdout: String::new(),
+                stderr: "Server is at capacity, retry shortly".to_string(),
+                exit_code: 1,
+                execution_time: 0.0,
+                truncated: false,
+                signal: None,
+                termination_reason: None,
+            });
+        }
+    };
+
     log::info!("Executing {} code ({} bytes)", req.language, req.code.len());
-    
+
     let start_time = std::time::Instant::now();
     let timeout = if req.timeout > 0 { req.timeout } else { 10 };
-    
+    let limits = ResourceLimits::from(&*req);
+
     let result = match req.language.as_str() {
-        "python" => execute_python(&req.code, timeout).await,
-        "javascript" | "typescript" => execute_javascript(&req.code, timeout).await,
-        "rust" => execute_rust(&req.code, timeout).await,
-        "go" => execute_go(&req.code, timeout).await,
-        "cpp" | "c++" => execute_cpp(&req.code, timeout).await,
-        "java" => execute_java(&req.code, timeout).await,
-        "c" => execute_c(&req.code, timeout).await,
-        "zig" => execute_zig(&req.code, timeout).await,
-        "elixir" => execute_elixir(&req.code, timeout).await,
-        "vlang" | "v" => execute_vlang(&req.code, timeout).await,
+        "python" => execute_python(&req.code, timeout, limits, req.sandbox, req.stdin.clone()).await,
+        "javascript" | "typescript" => execute_javascript(&req.code, timeout, limits, req.sandbox, req.stdin.clone()).await,
+        "rust" => execute_rust(&req.code, timeout, limits, req.sandbox, req.stdin.clone()).await,
+        "go" => execute_go(&req.code, timeout, limits, req.sandbox, req.stdin.clone()).await,
+        "cpp" | "c++" => execute_cpp(&req.code, timeout, limits, req.sandbox, req.stdin.clone()).await,
+        "java" => execute_java(&req.code, timeout, limits, req.sandbox, req.stdin.clone()).await,
+        "c" => execute_c(&req.code, timeout, limits, req.sandbox, req.stdin.clone()).await,
+        "zig" => execute_zig(&req.code, timeout, limits, req.sandbox, req.stdin.clone()).await,
+        "elixir" => execute_elixir(&req.code, timeout, limits, req.sandbox, req.stdin.clone()).await,
+        "vlang" | "v" => execute_vlang(&req.code, timeout, limits, req.sandbox, req.stdin.clone()).await,
         _ => Err(format!("Unsupported language: {}", req.language)),
     };
-    
+
     let execution_time = start_time.elapsed().as_secs_f64() * 1000.0;
-    
+
     match result {
-        Ok((stdout, stderr, exit_code)) => {
+        Ok((stdout, stderr, exit_code, truncated, signal, termination_reason)) => {
             HttpResponse::Ok().json(ExecuteResponse {
                 stdout,
                 stderr,
                 exit_code,
                 execution_time,
+                truncated,
+                signal,
+                termination_reason,
             })
         }
         Err(error) => {
@@ -84,92 +356,139 @@ async fn execute_code(req: web::Json<ExecuteRequest>) -> impl Responder {
                 stderr: error,
                 exit_code: 1,
                 execution_time,
+                truncated: false,
+                signal: None,
+                termination_reason: None,
             })
         }
     }
 }
 
-async fn execute_python(code: &str, timeout: u64) -> Result<(String, String, i32), String> {
+async fn execute_python(code: &str, timeout: u64, limits: ResourceLimits, sandbox: bool, stdin: Option<String>) -> Result<(String, String, i32, bool, Option<i32>, Option<String>), String> {
+    use std::fs;
     use std::process::{Command, Stdio};
-    
-    let child = Command::new("python3")
-        .arg("-c")
+    use uuid::Uuid;
+
+    // The sandbox needs a work dir to build the rootfs under even though
+    // Python code is passed inline via `-c` rather than written to a file.
+    let temp_dir = format!("/tmp/python_{}", Uuid::new_v4());
+    fs::create_dir_all(&temp_dir).map_err(|e| format!("Failed to create temp dir: {}", e))?;
+
+    let mut cmd = Command::new("python3");
+    cmd.arg("-c")
         .arg(code)
+        .stdin(Stdio::piped())
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| format!("Failed to start Python: {}", e))?;
-    
+        .stderr(Stdio::piped());
+    apply_resource_limits(&mut cmd, limits.clone());
+    set_process_group(&mut cmd);
+    jobserver::configure(&mut cmd, sandbox);
+    if sandbox {
+        sandbox::apply(&mut cmd, Path::new(&temp_dir)).map_err(|e| format!("Failed to prepare sandbox: {}", e))?;
+    }
+
+    let mut child = match cmd.spawn() {
+        Ok(c) => c,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&temp_dir);
+            return Err(format!("Failed to start Python: {}", e));
+        }
+    };
+    write_stdin(&mut child, &stdin);
+    let pid = child.id();
+
     // Wait with timeout
     let result = tokio::time::timeout(
         std::time::Duration::from_secs(timeout),
         tokio::task::spawn_blocking(move || child.wait_with_output()),
     )
     .await;
-    
+
+    let _ = fs::remove_dir_all(&temp_dir);
+
     match result {
-        Ok(Ok(Ok(output))) => {
-            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            let exit_code = output.status.code().unwrap_or(1);
-            Ok((stdout, stderr, exit_code))
-        }
+        Ok(Ok(Ok(output))) => Ok(truncate_output(output, limits.max_output_bytes)),
         Ok(Ok(Err(e))) => Err(format!("Process error: {}", e)),
         Ok(Err(e)) => Err(format!("Task error: {}", e)),
-        Err(_) => Err(format!("Execution timeout ({}s)", timeout)),
+        Err(_) => {
+            kill_process_group(pid);
+            Err(format!("Execution timeout ({}s)", timeout))
+        }
     }
 }
 
-async fn execute_javascript(code: &str, timeout: u64) -> Result<(String, String, i32), String> {
+async fn execute_javascript(code: &str, timeout: u64, limits: ResourceLimits, sandbox: bool, stdin: Option<String>) -> Result<(String, String, i32, bool, Option<i32>, Option<String>), String> {
+    use std::fs;
     use std::process::{Command, Stdio};
-    
-    let child = Command::new("node")
-        .arg("-e")
+    use uuid::Uuid;
+
+    // The sandbox needs a work dir to build the rootfs under even though
+    // JS code is passed inline via `-e` rather than written to a file.
+    let temp_dir = format!("/tmp/javascript_{}", Uuid::new_v4());
+    fs::create_dir_all(&temp_dir).map_err(|e| format!("Failed to create temp dir: {}", e))?;
+
+    let mut cmd = Command::new("node");
+    cmd.arg("-e")
         .arg(code)
+        .stdin(Stdio::piped())
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| format!("Failed to start Node.js: {}", e))?;
-    
+        .stderr(Stdio::piped());
+    apply_resource_limits(&mut cmd, limits.clone());
+    set_process_group(&mut cmd);
+    jobserver::configure(&mut cmd, sandbox);
+    if sandbox {
+        sandbox::apply(&mut cmd, Path::new(&temp_dir)).map_err(|e| format!("Failed to prepare sandbox: {}", e))?;
+    }
+
+    let mut child = match cmd.spawn() {
+        Ok(c) => c,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&temp_dir);
+            return Err(format!("Failed to start Node.js: {}", e));
+        }
+    };
+    write_stdin(&mut child, &stdin);
+    let pid = child.id();
+
     let result = tokio::time::timeout(
         std::time::Duration::from_secs(timeout),
         tokio::task::spawn_blocking(move || child.wait_with_output()),
     )
     .await;
-    
+
+    let _ = fs::remove_dir_all(&temp_dir);
+
     match result {
-        Ok(Ok(Ok(output))) => {
-            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            let exit_code = output.status.code().unwrap_or(1);
-            Ok((stdout, stderr, exit_code))
-        }
+        Ok(Ok(Ok(output))) => Ok(truncate_output(output, limits.max_output_bytes)),
         Ok(Ok(Err(e))) => Err(format!("Process error: {}", e)),
         Ok(Err(e)) => Err(format!("Task error: {}", e)),
-        Err(_) => Err(format!("Execution timeout ({}s)", timeout)),
+        Err(_) => {
+            kill_process_group(pid);
+            Err(format!("Execution timeout ({}s)", timeout))
+        }
     }
 }
 
-async fn execute_rust(code: &str, timeout: u64) -> Result<(String, String, i32), String> {
+async fn execute_rust(code: &str, timeout: u64, limits: ResourceLimits, sandbox: bool, stdin: Option<String>) -> Result<(String, String, i32, bool, Option<i32>, Option<String>), String> {
     use std::fs;
     use std::process::{Command, Stdio};
     use uuid::Uuid;
-    
+
     // Create temp directory for Rust code
     let temp_id = Uuid::new_v4().to_string();
     let temp_dir = format!("/tmp/rust_{}", temp_id);
     fs::create_dir_all(&temp_dir).map_err(|e| format!("Failed to create temp dir: {}", e))?;
-    
+
     let source_file = format!("{}/main.rs", temp_dir);
     fs::write(&source_file, code).map_err(|e| format!("Failed to write source: {}", e))?;
-    
+
     // Compile
     let compile = Command::new("rustc")
         .args(&[&source_file, "-o", &format!("{}/main", temp_dir)])
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .output();
-    
+
     let compile_output = match compile {
         Ok(output) => output,
         Err(e) => {
@@ -177,116 +496,128 @@ async fn execute_rust(code: &str, timeout: u64) -> Result<(String, String, i32),
             return Err(format!("Rust compiler not available: {}", e));
         }
     };
-    
+
     if !compile_output.status.success() {
         let stderr = String::from_utf8_lossy(&compile_output.stderr).to_string();
         let _ = fs::remove_dir_all(&temp_dir);
-        return Ok((String::new(), format!("Compilation error:\n{}", stderr), 1));
+        return Ok((String::new(), format!("Compilation error:\n{}", stderr), 1, false, None, None));
     }
-    
+
     // Execute
-    let child = Command::new(format!("{}/main", temp_dir))
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn();
-    
-    let child = match child {
+    let mut cmd = Command::new(format!("{}/main", temp_dir));
+    cmd.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+    apply_resource_limits(&mut cmd, limits.clone());
+    set_process_group(&mut cmd);
+    jobserver::configure(&mut cmd, sandbox);
+    if sandbox {
+        sandbox::apply(&mut cmd, Path::new(&temp_dir)).map_err(|e| format!("Failed to prepare sandbox: {}", e))?;
+    }
+    let child = cmd.spawn();
+
+    let mut child = match child {
         Ok(c) => c,
         Err(e) => {
             let _ = fs::remove_dir_all(&temp_dir);
             return Err(format!("Failed to execute: {}", e));
         }
     };
-    
+    write_stdin(&mut child, &stdin);
+    let pid = child.id();
+
     let result = tokio::time::timeout(
         std::time::Duration::from_secs(timeout),
         tokio::task::spawn_blocking(move || child.wait_with_output()),
     )
     .await;
-    
+
     let _ = fs::remove_dir_all(&temp_dir);
-    
+
     match result {
-        Ok(Ok(Ok(output))) => {
-            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            let exit_code = output.status.code().unwrap_or(1);
-            Ok((stdout, stderr, exit_code))
-        }
+        Ok(Ok(Ok(output))) => Ok(truncate_output(output, limits.max_output_bytes)),
         Ok(Ok(Err(e))) => Err(format!("Process error: {}", e)),
         Ok(Err(e)) => Err(format!("Task error: {}", e)),
-        Err(_) => Err(format!("Execution timeout ({}s)", timeout)),
+        Err(_) => {
+            kill_process_group(pid);
+            Err(format!("Execution timeout ({}s)", timeout))
+        }
     }
 }
 
-async fn execute_go(code: &str, timeout: u64) -> Result<(String, String, i32), String> {
+async fn execute_go(code: &str, timeout: u64, limits: ResourceLimits, sandbox: bool, stdin: Option<String>) -> Result<(String, String, i32, bool, Option<i32>, Option<String>), String> {
     use std::fs;
     use std::process::{Command, Stdio};
     use uuid::Uuid;
-    
+
     let temp_id = Uuid::new_v4().to_string();
     let temp_dir = format!("/tmp/go_{}", temp_id);
     fs::create_dir_all(&temp_dir).map_err(|e| format!("Failed to create temp dir: {}", e))?;
-    
+
     let source_file = format!("{}/main.go", temp_dir);
     fs::write(&source_file, code).map_err(|e| format!("Failed to write source: {}", e))?;
-    
+
     // Run go code directly
-    let child = Command::new("go")
-        .args(&["run", &source_file])
+    let mut cmd = Command::new("go");
+    cmd.args(&["run", &source_file])
+        .stdin(Stdio::piped())
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn();
-    
-    let child = match child {
+        .stderr(Stdio::piped());
+    apply_resource_limits(&mut cmd, limits.clone());
+    set_process_group(&mut cmd);
+    jobserver::configure(&mut cmd, sandbox);
+    if sandbox {
+        sandbox::apply(&mut cmd, Path::new(&temp_dir)).map_err(|e| format!("Failed to prepare sandbox: {}", e))?;
+    }
+    let child = cmd.spawn();
+
+    let mut child = match child {
         Ok(c) => c,
         Err(e) => {
             let _ = fs::remove_dir_all(&temp_dir);
             return Err(format!("Go compiler not available: {}", e));
         }
     };
-    
+    write_stdin(&mut child, &stdin);
+    let pid = child.id();
+
     let result = tokio::time::timeout(
         std::time::Duration::from_secs(timeout),
         tokio::task::spawn_blocking(move || child.wait_with_output()),
     )
     .await;
-    
+
     let _ = fs::remove_dir_all(&temp_dir);
-    
+
     match result {
-        Ok(Ok(Ok(output))) => {
-            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            let exit_code = output.status.code().unwrap_or(1);
-            Ok((stdout, stderr, exit_code))
-        }
+        Ok(Ok(Ok(output))) => Ok(truncate_output(output, limits.max_output_bytes)),
         Ok(Ok(Err(e))) => Err(format!("Process error: {}", e)),
         Ok(Err(e)) => Err(format!("Task error: {}", e)),
-        Err(_) => Err(format!("Execution timeout ({}s)", timeout)),
+        Err(_) => {
+            kill_process_group(pid);
+            Err(format!("Execution timeout ({}s)", timeout))
+        }
     }
 }
 
-async fn execute_cpp(code: &str, timeout: u64) -> Result<(String, String, i32), String> {
+async fn execute_cpp(code: &str, timeout: u64, limits: ResourceLimits, sandbox: bool, stdin: Option<String>) -> Result<(String, String, i32, bool, Option<i32>, Option<String>), String> {
     use std::fs;
     use std::process::{Command, Stdio};
     use uuid::Uuid;
-    
+
     let temp_id = Uuid::new_v4().to_string();
     let temp_dir = format!("/tmp/cpp_{}", temp_id);
     fs::create_dir_all(&temp_dir).map_err(|e| format!("Failed to create temp dir: {}", e))?;
-    
+
     let source_file = format!("{}/main.cpp", temp_dir);
     let binary_file = format!("{}/main", temp_dir);
     fs::write(&source_file, code).map_err(|e| format!("Failed to write source: {}", e))?;
-    
+
     // Compile with g++
     let compile = Command::new("g++")
         .args(&[&source_file, "-o", &binary_file, "-std=c++17"])
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .output();
-    
+
     let compile_output = match compile {
         Ok(output) => output,
         Err(e) => {
@@ -294,62 +625,67 @@ async fn execute_cpp(code: &str, timeout: u64) -> Result<(String, String, i32),
             return Err(format!("C++ compiler not available: {}", e));
         }
     };
-    
+
     if !compile_output.status.success() {
         let stderr = String::from_utf8_lossy(&compile_output.stderr).to_string();
         let _ = fs::remove_dir_all(&temp_dir);
-        return Ok((String::new(), format!("Compilation error:\n{}", stderr), 1));
+        return Ok((String::new(), format!("Compilation error:\n{}", stderr), 1, false, None, None));
     }
-    
+
     // Execute
-    let child = Command::new(&binary_file)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn();
-    
-    let child = match child {
+    let mut cmd = Command::new(&binary_file);
+    cmd.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+    apply_resource_limits(&mut cmd, limits.clone());
+    set_process_group(&mut cmd);
+    jobserver::configure(&mut cmd, sandbox);
+    if sandbox {
+        sandbox::apply(&mut cmd, Path::new(&temp_dir)).map_err(|e| format!("Failed to prepare sandbox: {}", e))?;
+    }
+    let child = cmd.spawn();
+
+    let mut child = match child {
         Ok(c) => c,
         Err(e) => {
             let _ = fs::remove_dir_all(&temp_dir);
             return Err(format!("Failed to execute: {}", e));
         }
     };
-    
+    write_stdin(&mut child, &stdin);
+    let pid = child.id();
+
     let result = tokio::time::timeout(
         std::time::Duration::from_secs(timeout),
         tokio::task::spawn_blocking(move || child.wait_with_output()),
     )
     .await;
-    
+
     let _ = fs::remove_dir_all(&temp_dir);
-    
+
     match result {
-        Ok(Ok(Ok(output))) => {
-            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            let exit_code = output.status.code().unwrap_or(1);
-            Ok((stdout, stderr, exit_code))
-        }
+        Ok(Ok(Ok(output))) => Ok(truncate_output(output, limits.max_output_bytes)),
         Ok(Ok(Err(e))) => Err(format!("Process error: {}", e)),
         Ok(Err(e)) => Err(format!("Task error: {}", e)),
-        Err(_) => Err(format!("Execution timeout ({}s)", timeout)),
+        Err(_) => {
+            kill_process_group(pid);
+            Err(format!("Execution timeout ({}s)", timeout))
+        }
     }
 }
 
-async fn execute_java(code: &str, timeout: u64) -> Result<(String, String, i32), String> {
+async fn execute_java(code: &str, timeout: u64, limits: ResourceLimits, sandbox: bool, stdin: Option<String>) -> Result<(String, String, i32, bool, Option<i32>, Option<String>), String> {
     use std::fs;
     use std::process::{Command, Stdio};
     use uuid::Uuid;
-    
+
     let temp_id = Uuid::new_v4().to_string();
     let temp_dir = format!("/tmp/java_{}", temp_id);
     fs::create_dir_all(&temp_dir).map_err(|e| format!("Failed to create temp dir: {}", e))?;
-    
+
     // Extract class name from code
     let class_name = extract_java_class_name(code).unwrap_or("Main".to_string());
     let source_file = format!("{}/{}.java", temp_dir, class_name);
     fs::write(&source_file, code).map_err(|e| format!("Failed to write source: {}", e))?;
-    
+
     // Compile
     let compile = Command::new("javac")
         .arg(&source_file)
@@ -357,7 +693,7 @@ async fn execute_java(code: &str, timeout: u64) -> Result<(String, String, i32),
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .output();
-    
+
     let compile_output = match compile {
         Ok(output) => output,
         Err(e) => {
@@ -365,70 +701,77 @@ async fn execute_java(code: &str, timeout: u64) -> Result<(String, String, i32),
             return Err(format!("Java compiler not available: {}", e));
         }
     };
-    
+
     if !compile_output.status.success() {
         let stderr = String::from_utf8_lossy(&compile_output.stderr).to_string();
         let _ = fs::remove_dir_all(&temp_dir);
-        return Ok((String::new(), format!("Compilation error:\n{}", stderr), 1));
+        return Ok((String::new(), format!("Compilation error:\n{}", stderr), 1, false, None, None));
     }
-    
+
     // Execute
-    let child = Command::new("java")
-        .arg(&class_name)
+    let mut cmd = Command::new("java");
+    cmd.arg(&class_name)
         .current_dir(&temp_dir)
+        .stdin(Stdio::piped())
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn();
-    
-    let child = match child {
+        .stderr(Stdio::piped());
+    apply_resource_limits(&mut cmd, limits.clone());
+    set_process_group(&mut cmd);
+    jobserver::configure(&mut cmd, sandbox);
+    if sandbox {
+        sandbox::apply(&mut cmd, Path::new(&temp_dir)).map_err(|e| format!("Failed to prepare sandbox: {}", e))?;
+    }
+    let child = cmd.spawn();
+
+    let mut child = match child {
         Ok(c) => c,
         Err(e) => {
             let _ = fs::remove_dir_all(&temp_dir);
             return Err(format!("Failed to execute: {}", e));
         }
     };
-    
+    write_stdin(&mut child, &stdin);
+    let pid = child.id();
+
     let result = tokio::time::timeout(
         std::time::Duration::from_secs(timeout),
         tokio::task::spawn_blocking(move || child.wait_with_output()),
     )
     .await;
-    
+
     let _ = fs::remove_dir_all(&temp_dir);
-    
+
     match result {
-        Ok(Ok(Ok(output))) => {
-            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            let exit_code = output.status.code().unwrap_or(1);
-            Ok((stdout, stderr, exit_code))
-        }
+        Ok(Ok(Ok(output))) => Ok(truncate_output(output, limits.max_output_bytes)),
         Ok(Ok(Err(e))) => Err(format!("Process error: {}", e)),
         Ok(Err(e)) => Err(format!("Task error: {}", e)),
-        Err(_) => Err(format!("Execution timeout ({}s)", timeout)),
+        Err(_) => {
+            kill_process_group(pid);
+            Err(format!("Execution timeout ({}s)", timeout))
+        }
     }
 }
 
-async fn execute_c(code: &str, timeout: u64) -> Result<(String, String, i32), String> {
+async fn execute_c(code: &str, timeout: u64, limits: ResourceLimits, sandbox: bool, stdin: Option<String>) -> Result<(String, String, i32, bool, Option<i32>, Option<String>), String> {
     use std::fs;
     use std::process::{Command, Stdio};
     use uuid::Uuid;
-    
+
     let temp_id = Uuid::new_v4().to_string();
     let temp_dir = format!("/tmp/c_{}", temp_id);
     fs::create_dir_all(&temp_dir).map_err(|e| format!("Failed to create temp dir: {}", e))?;
-    
+
     let source_file = format!("{}/main.c", temp_dir);
     let binary_file = format!("{}/main", temp_dir);
     fs::write(&source_file, code).map_err(|e| format!("Failed to write source: {}", e))?;
-    
+
     // Compile with gcc
     let compile = Command::new("gcc")
         .args(&[&source_file, "-o", &binary_file])
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .output();
-    
+
     let compile_output = match compile {
         Ok(output) => output,
         Err(e) => {
@@ -436,49 +779,54 @@ async fn execute_c(code: &str, timeout: u64) -> Result<(String, String, i32), St
             return Err(format!("C compiler not available: {}", e));
         }
     };
-    
+
     if !compile_output.status.success() {
         let stderr = String::from_utf8_lossy(&compile_output.stderr).to_string();
         let _ = fs::remove_dir_all(&temp_dir);
-        return Ok((String::new(), format!("Compilation error:\n{}", stderr), 1));
+        return Ok((String::new(), format!("Compilation error:\n{}", stderr), 1, false, None, None));
     }
-    
+
     // Execute
-    let child = Command::new(&binary_file)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn();
-    
-    let child = match child {
+    let mut cmd = Command::new(&binary_file);
+    cmd.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+    apply_resource_limits(&mut cmd, limits.clone());
+    set_process_group(&mut cmd);
+    jobserver::configure(&mut cmd, sandbox);
+    if sandbox {
+        sandbox::apply(&mut cmd, Path::new(&temp_dir)).map_err(|e| format!("Failed to prepare sandbox: {}", e))?;
+    }
+    let child = cmd.spawn();
+
+    let mut child = match child {
         Ok(c) => c,
         Err(e) => {
             let _ = fs::remove_dir_all(&temp_dir);
             return Err(format!("Failed to execute: {}", e));
         }
     };
-    
+    write_stdin(&mut child, &stdin);
+    let pid = child.id();
+
     let result = tokio::time::timeout(
         std::time::Duration::from_secs(timeout),
         tokio::task::spawn_blocking(move || child.wait_with_output()),
     )
     .await;
-    
+
     let _ = fs::remove_dir_all(&temp_dir);
-    
+
     match result {
-        Ok(Ok(Ok(output))) => {
-            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            let exit_code = output.status.code().unwrap_or(1);
-            Ok((stdout, stderr, exit_code))
-        }
+        Ok(Ok(Ok(output))) => Ok(truncate_output(output, limits.max_output_bytes)),
         Ok(Ok(Err(e))) => Err(format!("Process error: {}", e)),
         Ok(Err(e)) => Err(format!("Task error: {}", e)),
-        Err(_) => Err(format!("Execution timeout ({}s)", timeout)),
+        Err(_) => {
+            kill_process_group(pid);
+            Err(format!("Execution timeout ({}s)", timeout))
+        }
     }
 }
 
-fn extract_java_class_name(code: &str) -> Option<String> {
+pub(crate) fn extract_java_class_name(code: &str) -> Option<String> {
     // Simple regex to extract public class name
     for line in code.lines() {
         if line.contains("public class") {
@@ -493,23 +841,23 @@ fn extract_java_class_name(code: &str) -> Option<String> {
     None
 }
 
-async fn execute_zig(code: &str, timeout: u64) -> Result<(String, String, i32), String> {
+async fn execute_zig(code: &str, timeout: u64, limits: ResourceLimits, sandbox: bool, stdin: Option<String>) -> Result<(String, String, i32, bool, Option<i32>, Option<String>), String> {
     use std::fs;
     use std::process::{Command, Stdio};
     use uuid::Uuid;
-    
+
     // Create a temporary directory for Zig code
     let temp_dir = format!("/tmp/zig_{}", Uuid::new_v4());
     fs::create_dir_all(&temp_dir)
         .map_err(|e| format!("Failed to create temp directory: {}", e))?;
-    
+
     let file_path = format!("{}/main.zig", temp_dir);
     fs::write(&file_path, code)
         .map_err(|e| {
             let _ = fs::remove_dir_all(&temp_dir);
             format!("Failed to write Zig file: {}", e)
         })?;
-    
+
     // Compile Zig code
     let compile_output = Command::new("zig")
         .args(&["build-exe", "main.zig"])
@@ -517,7 +865,7 @@ async fn execute_zig(code: &str, timeout: u64) -> Result<(String, String, i32),
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .output();
-    
+
     let compile_result = match compile_output {
         Ok(output) => output,
         Err(e) => {
@@ -525,176 +873,203 @@ async fn execute_zig(code: &str, timeout: u64) -> Result<(String, String, i32),
             return Err(format!("Failed to compile Zig code: {}", e));
         }
     };
-    
+
     if !compile_result.status.success() {
         let stderr = String::from_utf8_lossy(&compile_result.stderr).to_string();
         let _ = fs::remove_dir_all(&temp_dir);
         return Err(format!("Zig compilation error:\n{}", stderr));
     }
-    
+
     // Execute the compiled binary
     let exe_path = format!("{}/main", temp_dir);
-    let mut child = match Command::new(&exe_path)
-        .current_dir(&temp_dir)
+    let mut cmd = Command::new(&exe_path);
+    cmd.current_dir(&temp_dir)
+        .stdin(Stdio::piped())
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-    {
+        .stderr(Stdio::piped());
+    apply_resource_limits(&mut cmd, limits.clone());
+    set_process_group(&mut cmd);
+    jobserver::configure(&mut cmd, sandbox);
+    if sandbox {
+        sandbox::apply(&mut cmd, Path::new(&temp_dir)).map_err(|e| format!("Failed to prepare sandbox: {}", e))?;
+    }
+    let child = cmd.spawn();
+
+    let mut child = match child {
         Ok(c) => c,
         Err(e) => {
             let _ = fs::remove_dir_all(&temp_dir);
             return Err(format!("Failed to execute Zig binary: {}", e));
         }
     };
-    
+    write_stdin(&mut child, &stdin);
+    let pid = child.id();
+
     let result = tokio::time::timeout(
         std::time::Duration::from_secs(timeout),
         tokio::task::spawn_blocking(move || child.wait_with_output()),
     )
     .await;
-    
+
     let _ = fs::remove_dir_all(&temp_dir);
-    
+
     match result {
-        Ok(Ok(Ok(output))) => {
-            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            let exit_code = output.status.code().unwrap_or(1);
-            Ok((stdout, stderr, exit_code))
-        }
+        Ok(Ok(Ok(output))) => Ok(truncate_output(output, limits.max_output_bytes)),
         Ok(Ok(Err(e))) => Err(format!("Process error: {}", e)),
         Ok(Err(e)) => Err(format!("Task error: {}", e)),
-        Err(_) => Err(format!("Execution timeout ({}s)", timeout)),
+        Err(_) => {
+            kill_process_group(pid);
+            Err(format!("Execution timeout ({}s)", timeout))
+        }
     }
 }
 
-async fn execute_elixir(code: &str, timeout: u64) -> Result<(String, String, i32), String> {
+async fn execute_elixir(code: &str, timeout: u64, limits: ResourceLimits, sandbox: bool, stdin: Option<String>) -> Result<(String, String, i32, bool, Option<i32>, Option<String>), String> {
     use std::fs;
     use std::process::{Command, Stdio};
     use uuid::Uuid;
-    
+
     // Create a temporary directory for Elixir code
     let temp_dir = format!("/tmp/elixir_{}", Uuid::new_v4());
     fs::create_dir_all(&temp_dir)
         .map_err(|e| format!("Failed to create temp directory: {}", e))?;
-    
+
     let file_path = format!("{}/main.exs", temp_dir);
     fs::write(&file_path, code)
         .map_err(|e| {
             let _ = fs::remove_dir_all(&temp_dir);
             format!("Failed to write Elixir file: {}", e)
         })?;
-    
+
     // Execute Elixir script
-    let mut child = match Command::new("elixir")
-        .arg("main.exs")
+    let mut cmd = Command::new("elixir");
+    cmd.arg("main.exs")
         .current_dir(&temp_dir)
+        .stdin(Stdio::piped())
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-    {
+        .stderr(Stdio::piped());
+    apply_resource_limits(&mut cmd, limits.clone());
+    set_process_group(&mut cmd);
+    jobserver::configure(&mut cmd, sandbox);
+    if sandbox {
+        sandbox::apply(&mut cmd, Path::new(&temp_dir)).map_err(|e| format!("Failed to prepare sandbox: {}", e))?;
+    }
+    let child = cmd.spawn();
+
+    let mut child = match child {
         Ok(c) => c,
         Err(e) => {
             let _ = fs::remove_dir_all(&temp_dir);
             return Err(format!("Failed to execute Elixir: {}", e));
         }
     };
-    
+    write_stdin(&mut child, &stdin);
+    let pid = child.id();
+
     let result = tokio::time::timeout(
         std::time::Duration::from_secs(timeout),
         tokio::task::spawn_blocking(move || child.wait_with_output()),
     )
     .await;
-    
+
     let _ = fs::remove_dir_all(&temp_dir);
-    
+
     match result {
-        Ok(Ok(Ok(output))) => {
-            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            let exit_code = output.status.code().unwrap_or(1);
-            Ok((stdout, stderr, exit_code))
-        }
+        Ok(Ok(Ok(output))) => Ok(truncate_output(output, limits.max_output_bytes)),
         Ok(Ok(Err(e))) => Err(format!("Process error: {}", e)),
         Ok(Err(e)) => Err(format!("Task error: {}", e)),
-        Err(_) => Err(format!("Execution timeout ({}s)", timeout)),
+        Err(_) => {
+            kill_process_group(pid);
+            Err(format!("Execution timeout ({}s)", timeout))
+        }
     }
 }
 
-async fn execute_vlang(code: &str, timeout: u64) -> Result<(String, String, i32), String> {
+async fn execute_vlang(code: &str, timeout: u64, limits: ResourceLimits, sandbox: bool, stdin: Option<String>) -> Result<(String, String, i32, bool, Option<i32>, Option<String>), String> {
     use std::fs;
     use std::process::{Command, Stdio};
     use uuid::Uuid;
-    
+
     // Create a temporary directory for V code
     let temp_dir = format!("/tmp/vlang_{}", Uuid::new_v4());
     fs::create_dir_all(&temp_dir)
         .map_err(|e| format!("Failed to create temp directory: {}", e))?;
-    
+
     let file_path = format!("{}/main.v", temp_dir);
     fs::write(&file_path, code)
         .map_err(|e| {
             let _ = fs::remove_dir_all(&temp_dir);
             format!("Failed to write V file: {}", e)
         })?;
-    
+
     // Execute V code directly (V can run scripts without explicit compilation step)
-    let mut child = match Command::new("v")
-        .args(&["run", "main.v"])
+    let mut cmd = Command::new("v");
+    cmd.args(&["run", "main.v"])
         .current_dir(&temp_dir)
+        .stdin(Stdio::piped())
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-    {
+        .stderr(Stdio::piped());
+    apply_resource_limits(&mut cmd, limits.clone());
+    set_process_group(&mut cmd);
+    jobserver::configure(&mut cmd, sandbox);
+    if sandbox {
+        sandbox::apply(&mut cmd, Path::new(&temp_dir)).map_err(|e| format!("Failed to prepare sandbox: {}", e))?;
+    }
+    let child = cmd.spawn();
+
+    let mut child = match child {
         Ok(c) => c,
         Err(e) => {
             let _ = fs::remove_dir_all(&temp_dir);
             return Err(format!("Failed to execute V: {}", e));
         }
     };
-    
+    write_stdin(&mut child, &stdin);
+    let pid = child.id();
+
     let result = tokio::time::timeout(
         std::time::Duration::from_secs(timeout),
         tokio::task::spawn_blocking(move || child.wait_with_output()),
     )
     .await;
-    
+
     let _ = fs::remove_dir_all(&temp_dir);
-    
+
     match result {
-        Ok(Ok(Ok(output))) => {
-            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            let exit_code = output.status.code().unwrap_or(1);
-            Ok((stdout, stderr, exit_code))
-        }
+        Ok(Ok(Ok(output))) => Ok(truncate_output(output, limits.max_output_bytes)),
         Ok(Ok(Err(e))) => Err(format!("Process error: {}", e)),
         Ok(Err(e)) => Err(format!("Task error: {}", e)),
-        Err(_) => Err(format!("Execution timeout ({}s)", timeout)),
+        Err(_) => {
+            kill_process_group(pid);
+            Err(format!("Execution timeout ({}s)", timeout))
+        }
     }
 }
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     env_logger::init_from_env(env_logger::Env::default().default_filter_or("info"));
-    
+
     let port = env::var("PORT").unwrap_or_else(|_| "8004".to_string());
     let bind_address = format!("0.0.0.0:{}", port);
-    
+
     log::info!("Starting Execution Service on {}", bind_address);
-    
+
     HttpServer::new(|| {
         let cors = Cors::default()
             .allow_any_origin()
             .allow_any_method()
             .allow_any_header()
             .max_age(3600);
-        
+
         App::new()
             .wrap(cors)
             .route("/", web::get().to(root))
             .route("/health", web::get().to(health))
             .route("/execute", web::post().to(execute_code))
+            .route("/execute/stream", web::post().to(streaming::execute_stream))
+            .route("/execute/interactive", web::get().to(pty::interactive_session))
+            .route("/execute/project", web::post().to(project::execute_project))
     })
     .bind(&bind_address)?
     .run()
@@ -0,0 +1,271 @@
+//! `/execute/project` — build and run a multi-file project from a tar upload.
+//!
+//! The single-file helpers in `main.rs` take one `code` string, which can't
+//! represent anything with multiple source files, headers, a `Cargo.toml`,
+//! or test fixtures (Java already works around this by regex-scraping the
+//! class name out of a single file). This route accepts a base64-encoded tar
+//! archive, extracts it into a fresh temp dir with guards against path
+//! traversal and oversized archives, then runs a caller-supplied `build`
+//! command followed by a `run` command inside that dir — reusing the same
+//! resource limits, process-group timeout/kill, jobserver and sandbox
+//! machinery the single-file helpers use.
+
+use std::path::{Component, Path};
+use std::process::Stdio;
+
+use actix_web::{web, HttpResponse, Responder};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::ResourceLimits;
+
+/// Caps the total uncompressed size of an extracted archive, independent of
+/// the per-process `max_output_bytes`/`memory_bytes` limits, so a tar bomb
+/// can't fill the host's disk before a single process even starts. Overridable
+/// via `PROJECT_MAX_ARCHIVE_BYTES`.
+fn max_archive_bytes() -> u64 {
+    std::env::var("PROJECT_MAX_ARCHIVE_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(50 * 1024 * 1024)
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectRequest {
+    /// Base64-encoded tar archive of the project's source files.
+    archive: String,
+    /// Command run once before `run`, e.g. `"cargo build --release"`. Skipped
+    /// entirely when absent.
+    #[serde(default)]
+    build: Option<String>,
+    /// Command that runs the project, e.g. `"cargo run --release"` or
+    /// `"./a.out"`.
+    run: String,
+    #[serde(default)]
+    timeout: u64,
+    #[serde(default)]
+    cpu_seconds: Option<u64>,
+    #[serde(default)]
+    memory_bytes: Option<u64>,
+    #[serde(default)]
+    max_output_bytes: Option<usize>,
+    #[serde(default)]
+    max_processes: Option<u64>,
+    #[serde(default = "crate::default_sandbox")]
+    sandbox: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ProjectResponse {
+    build_stdout: Option<String>,
+    build_stderr: Option<String>,
+    stdout: String,
+    stderr: String,
+    exit_code: i32,
+    execution_time: f64,
+    truncated: bool,
+    signal: Option<i32>,
+    termination_reason: Option<String>,
+}
+
+pub async fn execute_project(req: web::Json<ProjectRequest>) -> impl Responder {
+    let start_time = std::time::Instant::now();
+    let _permit = match crate::execution_semaphore().try_acquire() {
+        Ok(permit) => permit,
+        Err(_) => {
+            return HttpResponse::TooManyRequests().json(ProjectResponse {
+                build_stdout: None,
+                build_stderr: None,
+                stdout: String::new(),
+                stderr: "Server is at capacity, retry shortly".to_string(),
+                exit_code: 1,
+                execution_time: start_time.elapsed().as_secs_f64() * 1000.0,
+                truncated: false,
+                signal: None,
+                termination_reason: None,
+            });
+        }
+    };
+    let timeout = if req.timeout > 0 { req.timeout } else { 30 };
+    let limits = ResourceLimits {
+        cpu_seconds: req.cpu_seconds,
+        memory_bytes: req.memory_bytes,
+        max_output_bytes: req.max_output_bytes,
+        max_processes: req.max_processes,
+    };
+
+    let temp_dir = format!("/tmp/project_{}", Uuid::new_v4());
+    if let Err(e) = std::fs::create_dir_all(&temp_dir) {
+        return error_response(format!("Failed to create temp dir: {}", e), start_time);
+    }
+
+    if let Err(e) = extract_archive(&req.archive, &temp_dir) {
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        return error_response(e, start_time);
+    }
+
+    let mut build_stdout = None;
+    let mut build_stderr = None;
+    if let Some(build_cmd) = &req.build {
+        match run_step(build_cmd, &temp_dir, &limits, req.sandbox, timeout).await {
+            Ok((stdout, stderr, exit_code, ..)) if exit_code != 0 => {
+                let _ = std::fs::remove_dir_all(&temp_dir);
+                return HttpResponse::Ok().json(ProjectResponse {
+                    build_stdout: Some(stdout),
+                    build_stderr: Some(format!("Build failed:\n{}", stderr)),
+                    stdout: String::new(),
+                    stderr: String::new(),
+                    exit_code,
+                    execution_time: start_time.elapsed().as_secs_f64() * 1000.0,
+                    truncated: false,
+                    signal: None,
+                    termination_reason: None,
+                });
+            }
+            Ok((stdout, stderr, ..)) => {
+                build_stdout = Some(stdout);
+                build_stderr = Some(stderr);
+            }
+            Err(e) => {
+                let _ = std::fs::remove_dir_all(&temp_dir);
+                return error_response_with_build(e, build_stdout, build_stderr, start_time);
+            }
+        }
+    }
+
+    let result = run_step(&req.run, &temp_dir, &limits, req.sandbox, timeout).await;
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    let execution_time = start_time.elapsed().as_secs_f64() * 1000.0;
+
+    match result {
+        Ok((stdout, stderr, exit_code, truncated, signal, termination_reason)) => {
+            HttpResponse::Ok().json(ProjectResponse {
+                build_stdout,
+                build_stderr,
+                stdout,
+                stderr,
+                exit_code,
+                execution_time,
+                truncated,
+                signal,
+                termination_reason,
+            })
+        }
+        Err(e) => HttpResponse::Ok().json(ProjectResponse {
+            build_stdout,
+            build_stderr,
+            stdout: String::new(),
+            stderr: e,
+            exit_code: 1,
+            execution_time,
+            truncated: false,
+            signal: None,
+            termination_reason: None,
+        }),
+    }
+}
+
+fn error_response(error: String, start_time: std::time::Instant) -> HttpResponse {
+    error_response_with_build(error, None, None, start_time)
+}
+
+fn error_response_with_build(
+    error: String,
+    build_stdout: Option<String>,
+    build_stderr: Option<String>,
+    start_time: std::time::Instant,
+) -> HttpResponse {
+    HttpResponse::Ok().json(ProjectResponse {
+        build_stdout,
+        build_stderr,
+        stdout: String::new(),
+        stderr: error,
+        exit_code: 1,
+        execution_time: start_time.elapsed().as_secs_f64() * 1000.0,
+        truncated: false,
+        signal: None,
+        termination_reason: None,
+    })
+}
+
+/// Runs `shell_command` inside `work_dir` under the same resource limits,
+/// process-group timeout/kill, jobserver and sandbox as the single-file
+/// `execute_*` helpers in `main.rs`.
+async fn run_step(
+    shell_command: &str,
+    work_dir: &str,
+    limits: &ResourceLimits,
+    sandbox: bool,
+    timeout: u64,
+) -> Result<(String, String, i32, bool, Option<i32>, Option<String>), String> {
+    let mut cmd = std::process::Command::new("sh");
+    cmd.args(&["-c", shell_command])
+        .current_dir(work_dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    crate::apply_resource_limits(&mut cmd, limits.clone());
+    crate::set_process_group(&mut cmd);
+    crate::jobserver::configure(&mut cmd, sandbox);
+    if sandbox {
+        crate::sandbox::apply(&mut cmd, Path::new(work_dir))
+            .map_err(|e| format!("Failed to prepare sandbox: {}", e))?;
+    }
+
+    let child = cmd.spawn().map_err(|e| format!("Failed to start command: {}", e))?;
+    let pid = child.id();
+
+    let result = tokio::time::timeout(
+        std::time::Duration::from_secs(timeout),
+        tokio::task::spawn_blocking(move || child.wait_with_output()),
+    )
+    .await;
+
+    match result {
+        Ok(Ok(Ok(output))) => Ok(crate::truncate_output(output, limits.max_output_bytes)),
+        Ok(Ok(Err(e))) => Err(format!("Process error: {}", e)),
+        Ok(Err(e)) => Err(format!("Task error: {}", e)),
+        Err(_) => {
+            crate::kill_process_group(pid);
+            Err(format!("Execution timeout ({}s)", timeout))
+        }
+    }
+}
+
+/// Decodes `base64_data` and extracts it into `dest`, rejecting any entry
+/// that would escape `dest` (`..` components or absolute paths) and aborting
+/// once the total uncompressed size crosses `max_archive_bytes()`.
+fn extract_archive(base64_data: &str, dest: &str) -> Result<(), String> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(base64_data)
+        .map_err(|e| format!("Invalid base64 archive: {}", e))?;
+
+    let max_size = max_archive_bytes();
+    let mut total_size: u64 = 0;
+
+    let mut archive = tar::Archive::new(bytes.as_slice());
+    let entries = archive.entries().map_err(|e| format!("Invalid tar archive: {}", e))?;
+    for entry in entries {
+        let mut entry = entry.map_err(|e| format!("Invalid tar entry: {}", e))?;
+        let path = entry.path().map_err(|e| format!("Invalid entry path: {}", e))?.into_owned();
+
+        if path.is_absolute() || path.components().any(|c| matches!(c, Component::ParentDir)) {
+            return Err(format!("Archive entry escapes project directory: {}", path.display()));
+        }
+
+        let size = entry.header().size().map_err(|e| format!("Invalid entry header: {}", e))?;
+        total_size += size;
+        if total_size > max_size {
+            return Err(format!("Archive exceeds the {}-byte size cap", max_size));
+        }
+
+        let unpacked = entry
+            .unpack_in(dest)
+            .map_err(|e| format!("Failed to extract {}: {}", path.display(), e))?;
+        if !unpacked {
+            return Err(format!("Refused to extract unsafe entry: {}", path.display()));
+        }
+    }
+
+    Ok(())
+}
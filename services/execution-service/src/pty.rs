@@ -0,0 +1,294 @@
+//! `/execute/interactive` — a real pseudo-terminal session over WebSocket.
+//!
+//! `stdin` (see `write_stdin` in `main.rs`) covers programs that read once and
+//! exit, but REPLs (`python3`, `iex`) and anything that calls `isatty()`
+//! behave differently — or just deadlock — when stdin is a plain pipe. This
+//! route allocates a real PTY with `posix_openpt`, gives the child the slave side
+//! as its controlling terminal, and pumps bytes between the master side and a
+//! WebSocket connection so the client gets an actual interactive terminal.
+//! The first WebSocket message is JSON (`InteractiveRequest`) describing what
+//! to run; everything after that is either raw terminal bytes or a resize
+//! control message.
+
+use std::ffi::CStr;
+use std::io;
+use std::os::unix::io::{FromRawFd, RawFd};
+use std::os::unix::process::CommandExt;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{apply_resource_limits, sandbox, ResourceLimits};
+
+#[derive(Debug, Deserialize)]
+struct InteractiveRequest {
+    code: String,
+    language: String,
+    #[serde(default = "crate::default_sandbox")]
+    sandbox: bool,
+    #[serde(default = "default_cols")]
+    cols: u16,
+    #[serde(default = "default_rows")]
+    rows: u16,
+}
+
+fn default_cols() -> u16 {
+    80
+}
+
+fn default_rows() -> u16 {
+    24
+}
+
+/// Client -> server control message for forwarding terminal resizes; anything
+/// that isn't valid JSON in this shape is treated as raw keystrokes instead.
+#[derive(Debug, Deserialize)]
+struct ResizeMessage {
+    resize: Winsize,
+}
+
+#[derive(Debug, Deserialize)]
+struct Winsize {
+    cols: u16,
+    rows: u16,
+}
+
+pub async fn interactive_session(
+    req: HttpRequest,
+    body: web::Payload,
+) -> actix_web::Result<HttpResponse> {
+    let (response, mut session, mut msg_stream) = actix_ws::handle(&req, body)?;
+
+    actix_web::rt::spawn(async move {
+        use futures_util::StreamExt;
+
+        let first = loop {
+            match msg_stream.next().await {
+                Some(Ok(actix_ws::Message::Text(text))) => break text,
+                Some(Ok(actix_ws::Message::Close(_))) | None => return,
+                _ => continue,
+            }
+        };
+
+        let setup: InteractiveRequest = match serde_json::from_str(&first) {
+            Ok(req) => req,
+            Err(e) => {
+                let _ = session
+                    .text(format!("{{\"error\":\"invalid session request: {}\"}}", e))
+                    .await;
+                let _ = session.close(None).await;
+                return;
+            }
+        };
+
+        // Held for the lifetime of the session, same as every other route's
+        // permit — without it an interactive session bypasses the same
+        // concurrency cap that bounds `/execute`.
+        let _permit = match crate::execution_semaphore().try_acquire() {
+            Ok(permit) => permit,
+            Err(_) => {
+                let _ = session
+                    .text("{\"error\":\"Server is at capacity, retry shortly\"}")
+                    .await;
+                let _ = session.close(None).await;
+                return;
+            }
+        };
+
+        let limits = ResourceLimits::default();
+        let (master_fd, mut child, temp_dir) = match spawn_pty_child(&setup, &limits) {
+            Ok(spawned) => spawned,
+            Err(e) => {
+                let _ = session.text(format!("{{\"error\":\"{}\"}}", e)).await;
+                let _ = session.close(None).await;
+                return;
+            }
+        };
+
+        let master = unsafe { std::fs::File::from_raw_fd(master_fd) };
+        let master = tokio::fs::File::from_std(master);
+        let (mut master_read, mut master_write) = tokio::io::split(master);
+
+        let mut read_buf = [0u8; 4096];
+        loop {
+            tokio::select! {
+                read = tokio::io::AsyncReadExt::read(&mut master_read, &mut read_buf) => {
+                    match read {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => {
+                            if session.binary(read_buf[..n].to_vec()).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                }
+                msg = msg_stream.next() => {
+                    match msg {
+                        Some(Ok(actix_ws::Message::Binary(bytes))) => {
+                            use tokio::io::AsyncWriteExt;
+                            if master_write.write_all(&bytes).await.is_err() {
+                                break;
+                            }
+                        }
+                        Some(Ok(actix_ws::Message::Text(text))) => {
+                            if let Ok(resize) = serde_json::from_str::<ResizeMessage>(&text) {
+                                let _ = set_winsize(master_fd, resize.resize.rows, resize.resize.cols);
+                            } else {
+                                use tokio::io::AsyncWriteExt;
+                                if master_write.write_all(text.as_bytes()).await.is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                        Some(Ok(actix_ws::Message::Close(_))) | None => break,
+                        _ => {}
+                    }
+                }
+                _ = child.wait() => break,
+            }
+        }
+
+        let _ = child.kill().await;
+        let _ = child.wait().await;
+        if let Some(dir) = &temp_dir {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+        let _ = session.close(None).await;
+    });
+
+    Ok(response)
+}
+
+/// Opens a PTY, spawns the requested language's interpreter attached to the
+/// slave side as its controlling terminal, and hands back the master fd (for
+/// the byte pump) plus the child (as a `tokio::process::Child` so the session
+/// loop can `select!` on it) and the temp dir to clean up afterward.
+fn spawn_pty_child(
+    setup: &InteractiveRequest,
+    limits: &ResourceLimits,
+) -> Result<(RawFd, tokio::process::Child, Option<String>), String> {
+    let (master_fd, slave_fd) = open_pty().map_err(|e| format!("Failed to allocate PTY: {}", e))?;
+    set_winsize(master_fd, setup.rows, setup.cols).map_err(|e| format!("Failed to set window size: {}", e))?;
+
+    let (program, args, temp_dir) = resolve_interactive_command(&setup.language, &setup.code)?;
+
+    let mut cmd = Command::new(program);
+    cmd.args(&args);
+    if let Some(dir) = &temp_dir {
+        cmd.current_dir(dir);
+    }
+
+    unsafe {
+        cmd.stdin(Stdio::from_raw_fd(dup_fd(slave_fd)?));
+        cmd.stdout(Stdio::from_raw_fd(dup_fd(slave_fd)?));
+        cmd.stderr(Stdio::from_raw_fd(dup_fd(slave_fd)?));
+        cmd.pre_exec(move || {
+            if libc::setsid() < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            // Use the original slave fd (still open in the forked child,
+            // independent of where stdio redirection ends up putting the
+            // dup'd copies) to make the PTY the controlling terminal.
+            if libc::ioctl(slave_fd, libc::TIOCSCTTY as _, 0) < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+
+    apply_resource_limits(&mut cmd, limits.clone());
+    // No set_process_group here: pre_exec already calls setsid() above, which
+    // makes the child both a session leader and its own process-group
+    // leader. setpgid(0, 0) on a session leader fails EPERM (and the reverse
+    // ordering breaks setsid() instead), so stacking the two always fails
+    // one of them and spawn() errors out. setsid() already gives the child
+    // its own group; child.kill() below is enough to tear it down.
+    if setup.sandbox {
+        if let Some(dir) = &temp_dir {
+            sandbox::apply(&mut cmd, Path::new(dir))
+                .map_err(|e| format!("Failed to prepare sandbox: {}", e))?;
+        }
+    }
+
+    let mut tokio_cmd = tokio::process::Command::from(cmd);
+    let child = tokio_cmd
+        .spawn()
+        .map_err(|e| format!("Failed to start interactive session: {}", e))?;
+
+    unsafe {
+        libc::close(slave_fd);
+    }
+
+    Ok((master_fd, child, temp_dir))
+}
+
+/// Resolves the interactive program/args for the REPL-style languages this
+/// mode is meant for, writing `code` to a temp dir first when the language
+/// needs a source file on disk rather than a `-c`/`-e` flag.
+fn resolve_interactive_command(
+    language: &str,
+    code: &str,
+) -> Result<(String, Vec<String>, Option<String>), String> {
+    match language {
+        "python" => Ok(("python3".to_string(), vec!["-i".to_string(), "-c".to_string(), code.to_string()], None)),
+        "elixir" => {
+            let temp_dir = format!("/tmp/elixir_pty_{}", Uuid::new_v4());
+            std::fs::create_dir_all(&temp_dir).map_err(|e| format!("Failed to create temp dir: {}", e))?;
+            std::fs::write(format!("{}/main.exs", temp_dir), code)
+                .map_err(|e| format!("Failed to write source: {}", e))?;
+            Ok(("iex".to_string(), vec!["main.exs".to_string()], Some(temp_dir)))
+        }
+        "javascript" | "typescript" => Ok(("node".to_string(), vec!["-i".to_string(), "-e".to_string(), code.to_string()], None)),
+        other => Err(format!("Interactive mode doesn't support language: {}", other)),
+    }
+}
+
+fn open_pty() -> io::Result<(RawFd, RawFd)> {
+    let master_fd = unsafe { libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY) };
+    if master_fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    if unsafe { libc::grantpt(master_fd) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    if unsafe { libc::unlockpt(master_fd) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let mut name_buf = [0i8; 64];
+    if unsafe { libc::ptsname_r(master_fd, name_buf.as_mut_ptr(), name_buf.len()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let slave_name = unsafe { CStr::from_ptr(name_buf.as_ptr()) };
+
+    let slave_fd = unsafe { libc::open(slave_name.as_ptr(), libc::O_RDWR | libc::O_NOCTTY) };
+    if slave_fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok((master_fd, slave_fd))
+}
+
+fn dup_fd(fd: RawFd) -> Result<RawFd, String> {
+    let dup = unsafe { libc::dup(fd) };
+    if dup < 0 {
+        return Err(format!("Failed to duplicate PTY fd: {}", io::Error::last_os_error()));
+    }
+    Ok(dup)
+}
+
+fn set_winsize(fd: RawFd, rows: u16, cols: u16) -> io::Result<()> {
+    let ws = libc::winsize {
+        ws_row: rows,
+        ws_col: cols,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    if unsafe { libc::ioctl(fd, libc::TIOCSWINSZ as _, &ws) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
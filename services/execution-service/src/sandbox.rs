@@ -0,0 +1,228 @@
+//! Linux namespace sandbox for untrusted code execution.
+//!
+//! Every `execute_*` helper in `main.rs` routes its spawned child through
+//! [`apply`] unless the caller explicitly opts out. The child ends up in its
+//! own mount, network, IPC, UTS and user namespaces (the new PID namespace
+//! holds the processes it forks; `unshare` does not move the child itself
+//! into it), `pivot_root`ed into a
+//! minimal rootfs built from read-only bind mounts of the host toolchain
+//! directories plus a fresh tmpfs work dir, with no capabilities and
+//! `no_new_privs` set. The net effect: no outbound network, no view of the
+//! host filesystem beyond the toolchains it needs, and no visibility into
+//! other processes on the box.
+
+use std::ffi::{CStr, CString};
+use std::io;
+use std::os::unix::process::CommandExt;
+use std::path::Path;
+use std::process::Command;
+
+/// Read-only host directories bind-mounted into every sandbox rootfs so the
+/// language toolchains (`python3`, `rustc`, `gcc`, the JVM, ...) stay reachable
+/// without exposing the rest of the host filesystem.
+const TOOLCHAIN_DIRS: &[&str] = &["/usr", "/bin", "/lib", "/lib64", "/etc/alternatives"];
+
+/// A bind mount's source/destination, pre-converted to `CString`s (see
+/// [`Plan`]) so `enter` never has to allocate.
+struct BindMount {
+    src: CString,
+    dst: CString,
+}
+
+/// Every path `enter` needs, built and directory-created on the host in
+/// [`prepare`] before `spawn`. `enter` runs inside `pre_exec` — post-`fork`,
+/// pre-`exec`, in a single-threaded child — where only async-signal-safe
+/// calls are allowed: no heap allocation, no `fs::create_dir_all`, no
+/// `CString::new`. Splitting the plan out this way means `enter` only ever
+/// moves already-owned data into raw `unshare`/`mount`/`pivot_root`/`prctl`
+/// syscalls.
+struct Plan {
+    binds: Vec<BindMount>,
+    tmpfs_fstype: CString,
+    tmpfs_dst: CString,
+    pivot_new_root: CString,
+    pivot_old_root: CString,
+    old_root_unmount: CString,
+    root_chdir: CString,
+    work_chdir: CString,
+}
+
+/// Registers the `pre_exec` hook that moves the child into a fresh sandbox
+/// before `exec`. `work_dir` is the per-run temp dir the caller already
+/// created; it becomes the sandboxed `/work` and is torn down by the caller's
+/// existing `remove_dir_all` cleanup once the child exits. All directory
+/// creation and path/`CString` preparation happens here, in the parent,
+/// before the fork — the `pre_exec` closure only runs raw syscalls.
+pub fn apply(cmd: &mut Command, work_dir: &Path) -> io::Result<()> {
+    let plan = prepare(work_dir)?;
+    unsafe {
+        cmd.pre_exec(move || enter(&plan));
+    }
+    Ok(())
+}
+
+/// Lays out `<work_dir>/rootfs` with the toolchain dirs bind-mounted read-only
+/// and a `work/` subdir backed by a fresh tmpfs, and pre-converts every path
+/// `enter` will need into `CString`s.
+fn prepare(work_dir: &Path) -> io::Result<Plan> {
+    let root = work_dir.join("rootfs");
+    std::fs::create_dir_all(&root)?;
+
+    let mut binds = Vec::new();
+    for dir in TOOLCHAIN_DIRS {
+        let src = Path::new(dir);
+        if !src.exists() {
+            continue;
+        }
+        let dst = root.join(dir.trim_start_matches('/'));
+        std::fs::create_dir_all(&dst)?;
+        binds.push(BindMount {
+            src: path_to_cstring(src)?,
+            dst: path_to_cstring(&dst)?,
+        });
+    }
+
+    let work_target = root.join("work");
+    std::fs::create_dir_all(&work_target)?;
+
+    let old_root = root.join(".old_root");
+    std::fs::create_dir_all(&old_root)?;
+
+    Ok(Plan {
+        binds,
+        tmpfs_fstype: CString::new("tmpfs").unwrap(),
+        tmpfs_dst: path_to_cstring(&work_target)?,
+        pivot_new_root: path_to_cstring(&root)?,
+        pivot_old_root: path_to_cstring(&old_root)?,
+        old_root_unmount: CString::new("/.old_root").unwrap(),
+        root_chdir: CString::new("/").unwrap(),
+        work_chdir: CString::new("/work").unwrap(),
+    })
+}
+
+fn path_to_cstring(path: &Path) -> io::Result<CString> {
+    CString::new(path.as_os_str().to_string_lossy().as_bytes())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+/// Runs inside `pre_exec`: post-`fork`, pre-`exec`, single-threaded child.
+/// Everything it touches was already allocated in `prepare`, so this sticks
+/// to raw `unshare`/`mount`/`pivot_root`/`chdir`/`prctl` syscalls only.
+fn enter(plan: &Plan) -> io::Result<()> {
+    unshare_namespaces()?;
+
+    // pivot_root requires `new_root` to be a mount point sitting on a
+    // non-MS_SHARED mount. A freshly unshared namespace still shares its
+    // root's propagation with the host (MS_SHARED under systemd), and
+    // `rootfs` is just a plain host directory, not a mount point at all.
+    // Make the whole namespace private first, then bind-mount rootfs onto
+    // itself so it counts as its own mount point; without both, pivot_root
+    // fails EINVAL.
+    make_mount_private(&plan.root_chdir)?;
+    mount_raw(Some(&plan.pivot_new_root), &plan.pivot_new_root, None, libc::MS_BIND, None)?;
+
+    for bind in &plan.binds {
+        bind_mount_readonly(&bind.src, &bind.dst)?;
+    }
+    mount_raw(Some(&plan.tmpfs_fstype), &plan.tmpfs_dst, Some(&plan.tmpfs_fstype), 0, None)?;
+
+    pivot_into(plan)?;
+    drop_all_capabilities();
+    set_no_new_privs()?;
+    chdir(&plan.work_chdir)?;
+    Ok(())
+}
+
+/// Recursively flips the mount namespace's propagation to private so mounts
+/// made in here don't interact with the host's mount tree (see `enter`).
+fn make_mount_private(root: &CStr) -> io::Result<()> {
+    mount_raw(None, root, None, libc::MS_REC | libc::MS_PRIVATE, None)
+}
+
+fn unshare_namespaces() -> io::Result<()> {
+    let flags = libc::CLONE_NEWNS
+        | libc::CLONE_NEWPID
+        | libc::CLONE_NEWNET
+        | libc::CLONE_NEWIPC
+        | libc::CLONE_NEWUTS
+        | libc::CLONE_NEWUSER;
+    if unsafe { libc::unshare(flags) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+fn bind_mount_readonly(src: &CStr, dst: &CStr) -> io::Result<()> {
+    mount_raw(Some(src), dst, None, libc::MS_BIND, None)?;
+    // Read-only has to be applied as a remount: MS_BIND ignores MS_RDONLY on
+    // the initial mount.
+    mount_raw(Some(src), dst, None, libc::MS_BIND | libc::MS_REMOUNT | libc::MS_RDONLY, None)
+}
+
+fn mount_raw(src: Option<&CStr>, dst: &CStr, fstype: Option<&CStr>, flags: libc::c_ulong, data: Option<&CStr>) -> io::Result<()> {
+    let rc = unsafe {
+        libc::mount(
+            src.map_or(std::ptr::null(), |s| s.as_ptr()),
+            dst.as_ptr(),
+            fstype.map_or(std::ptr::null(), |s| s.as_ptr()),
+            flags,
+            data.map_or(std::ptr::null(), |d| d.as_ptr() as *const _),
+        )
+    };
+    if rc != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Swaps the mount namespace's root for the sandbox rootfs, stashing the old
+/// root under `.old_root` and lazily unmounting it immediately afterward so
+/// nothing outside the bind-mounted toolchain dirs stays reachable. Unlike
+/// the old implementation, the stray now-empty `.old_root` directory is left
+/// in place rather than `remove_dir`'d — that call isn't async-signal-safe,
+/// and the whole mount namespace (and everything in it) disappears with the
+/// child anyway.
+fn pivot_into(plan: &Plan) -> io::Result<()> {
+    let rc = unsafe {
+        libc::syscall(
+            libc::SYS_pivot_root,
+            plan.pivot_new_root.as_ptr(),
+            plan.pivot_old_root.as_ptr(),
+        )
+    };
+    if rc != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    chdir(&plan.root_chdir)?;
+    unmount_lazy(&plan.old_root_unmount)
+}
+
+fn unmount_lazy(path: &CStr) -> io::Result<()> {
+    if unsafe { libc::umount2(path.as_ptr(), libc::MNT_DETACH) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+fn chdir(path: &CStr) -> io::Result<()> {
+    if unsafe { libc::chdir(path.as_ptr()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+fn drop_all_capabilities() {
+    for cap in 0..=63 {
+        unsafe {
+            libc::prctl(libc::PR_CAPBSET_DROP, cap, 0, 0, 0);
+        }
+    }
+}
+
+fn set_no_new_privs() -> io::Result<()> {
+    if unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
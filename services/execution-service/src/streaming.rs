@@ -0,0 +1,397 @@
+//! `/execute/stream` — incremental stdout/stderr over Server-Sent Events.
+//!
+//! Unlike `/execute`, which buffers everything via `wait_with_output()` and
+//! only responds once the process exits, this route switches to
+//! `tokio::process::Command` with piped stdout/stderr, reads both pipes
+//! concurrently line-by-line, and emits each line as an SSE event
+//! (`stdout`/`stderr`) as soon as it arrives, followed by a final `exit` (or
+//! `timeout`) event. That gives the caller a live console instead of an
+//! all-or-nothing blob, and preserves partial output from runs that never
+//! terminate cleanly. The run step goes through the same resource limits,
+//! process-group timeout/kill, jobserver and sandbox machinery as the
+//! buffered `/execute` path — only the collection strategy differs.
+
+use std::path::Path;
+use std::process::Command as StdCommand;
+
+use actix_web::{web, HttpResponse, Responder};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::{ExecuteRequest, ResourceLimits};
+
+/// Builds an SSE frame: `event: <kind>\ndata: <data>\n\n`. Multi-line `data`
+/// (e.g. a compile error) is split into one `data:` field per line, as SSE
+/// framing requires.
+fn sse_event(kind: &str, data: &str) -> web::Bytes {
+    let mut frame = format!("event: {}\n", kind);
+    for line in data.split('\n') {
+        frame.push_str("data: ");
+        frame.push_str(line);
+        frame.push('\n');
+    }
+    frame.push('\n');
+    web::Bytes::from(frame)
+}
+
+async fn send(tx: &mpsc::Sender<Result<web::Bytes, actix_web::Error>>, kind: &str, data: &str) {
+    let _ = tx.send(Ok(sse_event(kind, data))).await;
+}
+
+pub async fn execute_stream(req: web::Json<ExecuteRequest>) -> impl Responder {
+    // Held for the lifetime of the spawned run below, not just this handler —
+    // otherwise `/execute/stream` would dodge the same concurrency cap that
+    // bounds `/execute`.
+    let permit = match crate::execution_semaphore().try_acquire() {
+        Ok(permit) => permit,
+        Err(_) => {
+            return HttpResponse::TooManyRequests()
+                .json(serde_json::json!({"error": "Server is at capacity, retry shortly"}));
+        }
+    };
+
+    let timeout = if req.timeout > 0 { req.timeout } else { 10 };
+    let language = req.language.clone();
+    let code = req.code.clone();
+    let limits = ResourceLimits::from(&*req);
+    let sandbox = req.sandbox;
+    let stdin = req.stdin.clone();
+
+    let (tx, rx) = mpsc::channel::<Result<web::Bytes, actix_web::Error>>(64);
+
+    tokio::spawn(async move {
+        let _permit = permit;
+        run_and_stream(&language, &code, timeout, limits, sandbox, stdin, tx).await;
+    });
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .streaming(ReceiverStream::new(rx))
+}
+
+async fn run_and_stream(
+    language: &str,
+    code: &str,
+    timeout: u64,
+    limits: ResourceLimits,
+    sandbox: bool,
+    stdin: Option<String>,
+    tx: mpsc::Sender<Result<web::Bytes, actix_web::Error>>,
+) {
+    let prepared = match prepare_run_command(language, code, &limits, sandbox).await {
+        Ok(prepared) => prepared,
+        Err(e) => {
+            send(&tx, "stderr", &e).await;
+            send(&tx, "exit", "{\"exit_code\":1}").await;
+            return;
+        }
+    };
+    let (mut cmd, temp_dir) = prepared;
+
+    let mut child = match cmd.spawn() {
+        Ok(c) => c,
+        Err(e) => {
+            send(&tx, "stderr", &format!("Failed to start {}: {}", language, e)).await;
+            send(&tx, "exit", "{\"exit_code\":1}").await;
+            if let Some(dir) = &temp_dir {
+                let _ = std::fs::remove_dir_all(dir);
+            }
+            return;
+        }
+    };
+    let pid = child.id();
+    write_stdin(&mut child, &stdin).await;
+
+    let stdout = child.stdout.take().expect("stdout piped");
+    let stderr = child.stderr.take().expect("stderr piped");
+    let mut stdout_lines = BufReader::new(stdout).lines();
+    let mut stderr_lines = BufReader::new(stderr).lines();
+    let mut stdout_done = false;
+    let mut stderr_done = false;
+
+    // `child.wait()` resolving doesn't mean stdout/stderr are fully drained —
+    // a quick-exiting process can leave lines still sitting in the
+    // `BufReader`s. Keep looping on the two line-read arms until both report
+    // EOF, only letting the wait arm fire once (captured in `exit_status`),
+    // so no buffered output is dropped on a fast exit.
+    let run = async {
+        let mut exit_status = None;
+        loop {
+            if stdout_done && stderr_done {
+                break match exit_status {
+                    Some(status) => status,
+                    None => child.wait().await,
+                };
+            }
+            tokio::select! {
+                line = stdout_lines.next_line(), if !stdout_done => {
+                    match line {
+                        Ok(Some(l)) => send(&tx, "stdout", &l).await,
+                        _ => stdout_done = true,
+                    }
+                }
+                line = stderr_lines.next_line(), if !stderr_done => {
+                    match line {
+                        Ok(Some(l)) => send(&tx, "stderr", &l).await,
+                        _ => stderr_done = true,
+                    }
+                }
+                status = child.wait(), if exit_status.is_none() => {
+                    exit_status = Some(status);
+                }
+            }
+        }
+    };
+
+    match tokio::time::timeout(std::time::Duration::from_secs(timeout), run).await {
+        Ok(Ok(status)) => {
+            let exit_code = status
+                .code()
+                .map(|c| c.to_string())
+                .unwrap_or_else(|| "null".to_string());
+            send(&tx, "exit", &format!("{{\"exit_code\":{}}}", exit_code)).await;
+        }
+        Ok(Err(e)) => {
+            send(&tx, "exit", &format!("{{\"exit_code\":null,\"error\":\"{}\"}}", e)).await;
+        }
+        Err(_) => {
+            // Kill the whole process group, not just the direct child —
+            // otherwise a compiler the run step shells out to (e.g. `go
+            // run`'s build step) survives the timeout, same leak chunk0-2
+            // fixed for the buffered path. Falls back to start_kill() only
+            // if we never got a pid (spawn raced a near-immediate exit).
+            match pid {
+                Some(pid) => crate::kill_process_group(pid),
+                None => {
+                    let _ = child.start_kill();
+                }
+            }
+            let _ = child.wait().await;
+            send(&tx, "timeout", &format!("Execution timeout ({}s)", timeout)).await;
+        }
+    }
+
+    if let Some(dir) = &temp_dir {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
+
+/// Produces the final, already-compiled (if applicable) run command for a
+/// language, plus the temp dir (if any) the caller should clean up once the
+/// child exits. Compilation itself isn't streamed — only the run step is.
+/// The returned command has resource limits, a process group, the jobserver
+/// and (if requested) the namespace sandbox already applied, same as the
+/// buffered `execute_*` helpers in `main.rs`.
+async fn prepare_run_command(
+    language: &str,
+    code: &str,
+    limits: &ResourceLimits,
+    sandbox: bool,
+) -> Result<(Command, Option<String>), String> {
+    use uuid::Uuid;
+
+    let (mut cmd, temp_dir): (StdCommand, Option<String>) = match language {
+        "python" => {
+            // The sandbox needs a work dir to build the rootfs under even
+            // though Python code is passed inline via `-c` rather than
+            // written to a file.
+            let temp_dir = format!("/tmp/python_stream_{}", Uuid::new_v4());
+            tokio::fs::create_dir_all(&temp_dir)
+                .await
+                .map_err(|e| format!("Failed to create temp dir: {}", e))?;
+            let mut cmd = StdCommand::new("python3");
+            cmd.arg("-c").arg(code);
+            (cmd, Some(temp_dir))
+        }
+        "javascript" | "typescript" => {
+            let temp_dir = format!("/tmp/javascript_stream_{}", Uuid::new_v4());
+            tokio::fs::create_dir_all(&temp_dir)
+                .await
+                .map_err(|e| format!("Failed to create temp dir: {}", e))?;
+            let mut cmd = StdCommand::new("node");
+            cmd.arg("-e").arg(code);
+            (cmd, Some(temp_dir))
+        }
+        "elixir" => {
+            let temp_dir = format!("/tmp/elixir_stream_{}", Uuid::new_v4());
+            tokio::fs::create_dir_all(&temp_dir)
+                .await
+                .map_err(|e| format!("Failed to create temp dir: {}", e))?;
+            tokio::fs::write(format!("{}/main.exs", temp_dir), code)
+                .await
+                .map_err(|e| format!("Failed to write source: {}", e))?;
+            let mut cmd = StdCommand::new("elixir");
+            cmd.arg("main.exs").current_dir(&temp_dir);
+            (cmd, Some(temp_dir))
+        }
+        "vlang" | "v" => {
+            let temp_dir = format!("/tmp/vlang_stream_{}", Uuid::new_v4());
+            tokio::fs::create_dir_all(&temp_dir)
+                .await
+                .map_err(|e| format!("Failed to create temp dir: {}", e))?;
+            tokio::fs::write(format!("{}/main.v", temp_dir), code)
+                .await
+                .map_err(|e| format!("Failed to write source: {}", e))?;
+            let mut cmd = StdCommand::new("v");
+            cmd.args(&["run", "main.v"]).current_dir(&temp_dir);
+            (cmd, Some(temp_dir))
+        }
+        "go" => {
+            let temp_dir = format!("/tmp/go_stream_{}", Uuid::new_v4());
+            tokio::fs::create_dir_all(&temp_dir)
+                .await
+                .map_err(|e| format!("Failed to create temp dir: {}", e))?;
+            let source_file = format!("{}/main.go", temp_dir);
+            tokio::fs::write(&source_file, code)
+                .await
+                .map_err(|e| format!("Failed to write source: {}", e))?;
+            let mut cmd = StdCommand::new("go");
+            cmd.args(&["run", &source_file]);
+            (cmd, Some(temp_dir))
+        }
+        "rust" => {
+            let temp_dir = format!("/tmp/rust_stream_{}", Uuid::new_v4());
+            let binary = format!("{}/main", temp_dir);
+            compile(
+                &temp_dir,
+                "main.rs",
+                code,
+                "rustc",
+                &[format!("{}/main.rs", temp_dir), "-o".to_string(), binary.clone()],
+            )
+            .await?;
+            let cmd = StdCommand::new(&binary);
+            (cmd, Some(temp_dir))
+        }
+        "c" => {
+            let temp_dir = format!("/tmp/c_stream_{}", Uuid::new_v4());
+            let binary = format!("{}/main", temp_dir);
+            compile(
+                &temp_dir,
+                "main.c",
+                code,
+                "gcc",
+                &[format!("{}/main.c", temp_dir), "-o".to_string(), binary.clone()],
+            )
+            .await?;
+            let cmd = StdCommand::new(&binary);
+            (cmd, Some(temp_dir))
+        }
+        "cpp" | "c++" => {
+            let temp_dir = format!("/tmp/cpp_stream_{}", Uuid::new_v4());
+            let binary = format!("{}/main", temp_dir);
+            compile(
+                &temp_dir,
+                "main.cpp",
+                code,
+                "g++",
+                &[
+                    format!("{}/main.cpp", temp_dir),
+                    "-o".to_string(),
+                    binary.clone(),
+                    "-std=c++17".to_string(),
+                ],
+            )
+            .await?;
+            let cmd = StdCommand::new(&binary);
+            (cmd, Some(temp_dir))
+        }
+        "zig" => {
+            let temp_dir = format!("/tmp/zig_stream_{}", Uuid::new_v4());
+            compile(
+                &temp_dir,
+                "main.zig",
+                code,
+                "zig",
+                &["build-exe".to_string(), "main.zig".to_string()],
+            )
+            .await?;
+            let mut cmd = StdCommand::new(format!("{}/main", temp_dir));
+            cmd.current_dir(&temp_dir);
+            (cmd, Some(temp_dir))
+        }
+        "java" => {
+            let temp_dir = format!("/tmp/java_stream_{}", Uuid::new_v4());
+            let class_name = crate::extract_java_class_name(code).unwrap_or("Main".to_string());
+            let source_file = format!("{}.java", class_name);
+            compile(
+                &temp_dir,
+                &source_file,
+                code,
+                "javac",
+                &[source_file.clone()],
+            )
+            .await?;
+            let mut cmd = StdCommand::new("java");
+            cmd.arg(&class_name).current_dir(&temp_dir);
+            (cmd, Some(temp_dir))
+        }
+        other => return Err(format!("Unsupported language: {}", other)),
+    };
+
+    pipe(&mut cmd);
+    crate::apply_resource_limits(&mut cmd, limits.clone());
+    crate::set_process_group(&mut cmd);
+    crate::jobserver::configure(&mut cmd, sandbox);
+    if sandbox {
+        let work_dir = temp_dir.clone().unwrap_or_else(|| "/tmp".to_string());
+        crate::sandbox::apply(&mut cmd, Path::new(&work_dir))
+            .map_err(|e| format!("Failed to prepare sandbox: {}", e))?;
+    }
+
+    Ok((Command::from(cmd), temp_dir))
+}
+
+fn pipe(cmd: &mut StdCommand) {
+    cmd.stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+}
+
+/// Writes `input` (if any) to the child's stdin and drops the handle so the
+/// child sees EOF instead of hanging forever on its next read — the async
+/// counterpart to `write_stdin` in `main.rs`, since streaming spawns a
+/// `tokio::process::Child` rather than a `std::process::Child`.
+async fn write_stdin(child: &mut tokio::process::Child, input: &Option<String>) {
+    if let Some(data) = input {
+        if let Some(mut stdin) = child.stdin.take() {
+            use tokio::io::AsyncWriteExt;
+            let _ = stdin.write_all(data.as_bytes()).await;
+        }
+    }
+}
+
+/// Writes `code` to `<temp_dir>/<file_name>` and runs `compiler args...`
+/// inside `temp_dir`, surfacing a compile error as a regular `Err` since
+/// there's nothing incremental to stream about compilation itself.
+async fn compile(
+    temp_dir: &str,
+    file_name: &str,
+    code: &str,
+    compiler: &str,
+    args: &[String],
+) -> Result<(), String> {
+    tokio::fs::create_dir_all(temp_dir)
+        .await
+        .map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    tokio::fs::write(format!("{}/{}", temp_dir, file_name), code)
+        .await
+        .map_err(|e| format!("Failed to write source: {}", e))?;
+
+    let output = Command::new(compiler)
+        .args(args)
+        .current_dir(temp_dir)
+        .output()
+        .await
+        .map_err(|e| format!("{} not available: {}", compiler, e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Compilation error:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    Ok(())
+}